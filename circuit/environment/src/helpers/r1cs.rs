@@ -19,38 +19,72 @@ use crate::{
 };
 use snarkvm_algorithms::r1cs::LookupTable;
 use snarkvm_fields::PrimeField;
+use snarkvm_utilities::{FromBytes, ToBytes};
 
-use std::rc::Rc;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{Read, Result as IoResult, Write},
+    rc::Rc,
+};
 
 pub type Scope = String;
 
-#[derive(Debug)]
+/// A sink that receives each constraint as it is enforced, instead of it being retained by the
+/// constraint system. This lets a single-pass consumer (e.g. one that streams the sparse rows
+/// from [`R1CS::to_matrices`] straight to a file, or one that only needs the running counts)
+/// process millions of constraints without holding them all in memory at once, mirroring how
+/// bellman's prover consumes constraints in one sweep.
+pub trait ConstraintSink<F: PrimeField> {
+    /// Receives a constraint as it is enforced.
+    fn push_constraint(&mut self, constraint: &Constraint<F>);
+
+    /// Receives a lookup constraint as it is enforced.
+    fn push_lookup(&mut self, constraint: &LookupConstraint<F>);
+}
+
 pub struct R1CS<F: PrimeField> {
     constants: Vec<Variable<F>>,
     public: Vec<Variable<F>>,
     private: Vec<Variable<F>>,
     constraints: Vec<Constraint<F>>,
+    constraint_scopes: Vec<Scope>,
     lookup_constraints: Vec<LookupConstraint<F>>,
+    lookup_constraint_scopes: Vec<Scope>,
     counter: Counter<F>,
     pub tables: Vec<LookupTable<F>>,
     nonzeros: (u64, u64, u64),
+    num_constraints: u64,
+    num_lookup_constraints: u64,
+    /// When set, `enforce`/`enforce_lookup` forward constraints here instead of retaining them.
+    sink: Option<Box<dyn ConstraintSink<F>>>,
 }
 
 impl<F: PrimeField> R1CS<F> {
-    /// Returns a new instance of a constraint system.
+    /// Returns a new instance of a constraint system, which retains every constraint in memory.
     pub fn new() -> Self {
         Self {
             constants: Default::default(),
             public: vec![Variable::Public(0u64, Rc::new(F::one()))],
             private: Default::default(),
             constraints: Default::default(),
+            constraint_scopes: Default::default(),
             lookup_constraints: Default::default(),
+            lookup_constraint_scopes: Default::default(),
             counter: Default::default(),
             tables: Default::default(),
             nonzeros: (0, 0, 0),
+            num_constraints: 0,
+            num_lookup_constraints: 0,
+            sink: None,
         }
     }
 
+    /// Returns a new instance of a constraint system that streams every enforced constraint to
+    /// the given sink instead of retaining it, bounding memory use on very large circuits.
+    pub fn new_streaming(sink: Box<dyn ConstraintSink<F>>) -> Self {
+        Self { sink: Some(sink), ..Self::new() }
+    }
+
     pub fn add_lookup_table(&mut self, table: LookupTable<F>) {
         self.tables.push(table);
     }
@@ -95,8 +129,18 @@ impl<F: PrimeField> R1CS<F> {
         self.nonzeros.0 += a_nonzeros;
         self.nonzeros.1 += b_nonzeros;
         self.nonzeros.2 += c_nonzeros;
-
-        self.constraints.push(constraint.clone());
+        self.num_constraints += 1;
+
+        match &mut self.sink {
+            // In streaming mode, forward the constraint to the sink instead of retaining it.
+            Some(sink) => sink.push_constraint(&constraint),
+            // Otherwise, tag the constraint with the scope it was created in, so a failing
+            // constraint can be traced back to it, and retain it as before.
+            None => {
+                self.constraint_scopes.push(self.counter.scope());
+                self.constraints.push(constraint.clone());
+            }
+        }
         self.counter.add_constraint(constraint);
     }
 
@@ -106,14 +150,74 @@ impl<F: PrimeField> R1CS<F> {
         self.nonzeros.0 += a_nonzeros;
         self.nonzeros.1 += b_nonzeros;
         self.nonzeros.2 += c_nonzeros;
-
-        self.lookup_constraints.push(constraint.clone());
+        self.num_lookup_constraints += 1;
+
+        match &mut self.sink {
+            Some(sink) => sink.push_lookup(&constraint),
+            None => {
+                self.lookup_constraint_scopes.push(self.counter.scope());
+                self.lookup_constraints.push(constraint.clone());
+            }
+        }
         self.counter.add_lookup_constraint(constraint);
     }
 
     /// Returns `true` if all constraints in the environment are satisfied.
+    ///
+    /// This also checks `self.lookup_constraints`, not just `self.constraints` as before: a
+    /// circuit is only fully satisfied if its lookup constraints hold too, so this is an
+    /// intentional strengthening, not an incidental change.
+    ///
+    /// # Panics
+    /// Panics if the constraint system is in streaming mode, since constraints are not retained
+    /// there; check satisfiability via the registered [`ConstraintSink`] instead.
     pub fn is_satisfied(&self) -> bool {
+        assert!(
+            self.sink.is_none(),
+            "`is_satisfied` is not supported in streaming mode; check satisfiability via the registered `ConstraintSink` instead"
+        );
+
+        // Note: `R1CS` stores variables as `Rc<F>`, which is `!Sync`, so constraints are not safe
+        // to evaluate via rayon's `par_iter`; this environment is single-threaded by design.
         self.constraints.iter().all(|constraint| constraint.is_satisfied())
+            && self.lookup_constraints.iter().all(|constraint| constraint.is_satisfied())
+    }
+
+    /// Returns the lowest-indexed unsatisfied constraint, together with the scope it was enforced in,
+    /// or `None` iff both `self.constraints` and `self.lookup_constraints` are satisfied (matching
+    /// the same contract as [`Self::is_satisfied`]). Regular constraints are checked first: if one
+    /// of them is unsatisfied, its index (into `self.constraints`) is returned; otherwise, the
+    /// lowest-indexed unsatisfied lookup constraint (indexed into `self.lookup_constraints`) is
+    /// returned instead.
+    ///
+    /// # Panics
+    /// Panics if the constraint system is in streaming mode, since constraints are not retained
+    /// there; check satisfiability via the registered [`ConstraintSink`] instead.
+    pub fn first_unsatisfied(&self) -> Option<(u64, Scope)> {
+        assert!(
+            self.sink.is_none(),
+            "`first_unsatisfied` is not supported in streaming mode; check satisfiability via the registered `ConstraintSink` instead"
+        );
+
+        let first_unsatisfied_constraint = self
+            .constraints
+            .iter()
+            .enumerate()
+            .filter_map(|(index, constraint)| {
+                (!constraint.is_satisfied()).then(|| (index as u64, self.constraint_scopes[index].clone()))
+            })
+            .min_by_key(|(index, _)| *index);
+
+        first_unsatisfied_constraint.or_else(|| {
+            self.lookup_constraints
+                .iter()
+                .enumerate()
+                .filter_map(|(index, constraint)| {
+                    (!constraint.is_satisfied())
+                        .then(|| (index as u64, self.lookup_constraint_scopes[index].clone()))
+                })
+                .min_by_key(|(index, _)| *index)
+        })
     }
 
     /// Returns `true` if all constraints in the current scope are satisfied.
@@ -143,12 +247,12 @@ impl<F: PrimeField> R1CS<F> {
 
     /// Returns the number of constraints in the constraint system.
     pub fn num_constraints(&self) -> u64 {
-        self.constraints.len() as u64
+        self.num_constraints
     }
 
     /// Returns the number of lookup constraints in the constraint system.
     pub fn num_lookup_constraints(&self) -> u64 {
-        self.lookup_constraints.len() as u64
+        self.num_lookup_constraints
     }
 
     /// Returns the number of nonzeros in the constraint system.
@@ -205,6 +309,91 @@ impl<F: PrimeField> R1CS<F> {
     pub fn to_lookup_constraints(&self) -> &Vec<LookupConstraint<F>> {
         &self.lookup_constraints
     }
+
+    /// Returns the column index of the given variable, under the canonical witness ordering
+    /// `[one, public_0.., private_0..]`. A constant variable folds into the leading `one` column.
+    fn to_column_index(&self, variable: &Variable<F>) -> usize {
+        match variable {
+            Variable::Constant(..) => 0,
+            Variable::Public(index, ..) => *index as usize,
+            Variable::Private(index, ..) => self.public.len() + *index as usize,
+        }
+    }
+
+    /// Decomposes a linear combination into its sparse `(column, coefficient)` terms, in the
+    /// canonical witness ordering. The linear combination's constant (tracked separately from its
+    /// terms) is folded into the leading `one` column.
+    fn to_sparse_row(&self, lc: &LinearCombination<F>) -> Vec<(usize, F)> {
+        let mut row: Vec<(usize, F)> =
+            lc.to_terms().iter().map(|(variable, coefficient)| (self.to_column_index(variable), *coefficient)).collect();
+
+        let constant = lc.to_constant();
+        if !constant.is_zero() {
+            row.push((0, constant));
+        }
+
+        row
+    }
+
+    /// Materializes the constraint system into the sparse `A`, `B`, `C` matrices (plus the ordered
+    /// witness vector) expected by external SNARK backends, e.g. to build a QAP the way bellman's
+    /// generator consumes a `ConstraintSystem`.
+    ///
+    /// # Panics
+    /// Panics if the constraint system is in streaming mode, since constraints are not retained
+    /// there; stream the sparse rows directly via a [`ConstraintSink`] instead.
+    pub fn to_matrices(&self) -> R1CSMatrices<F> {
+        assert!(
+            self.sink.is_none(),
+            "`to_matrices` is not supported in streaming mode; stream the sparse rows via a `ConstraintSink` instead"
+        );
+
+        let mut a = Vec::with_capacity(self.constraints.len());
+        let mut b = Vec::with_capacity(self.constraints.len());
+        let mut c = Vec::with_capacity(self.constraints.len());
+
+        for constraint in &self.constraints {
+            a.push(self.to_sparse_row(constraint.a()));
+            b.push(self.to_sparse_row(constraint.b()));
+            c.push(self.to_sparse_row(constraint.c()));
+        }
+
+        let witness = self
+            .public
+            .iter()
+            .chain(self.private.iter())
+            .map(|variable| *variable.value())
+            .collect();
+
+        R1CSMatrices {
+            a,
+            b,
+            c,
+            witness,
+            num_instance_variables: self.public.len(),
+            num_witness_variables: self.private.len(),
+        }
+    }
+}
+
+impl<F: PrimeField> fmt::Debug for R1CS<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("R1CS")
+            .field("constants", &self.constants)
+            .field("public", &self.public)
+            .field("private", &self.private)
+            .field("constraints", &self.constraints)
+            .field("constraint_scopes", &self.constraint_scopes)
+            .field("lookup_constraints", &self.lookup_constraints)
+            .field("lookup_constraint_scopes", &self.lookup_constraint_scopes)
+            .field("counter", &self.counter)
+            .field("tables", &self.tables)
+            .field("nonzeros", &self.nonzeros)
+            .field("num_constraints", &self.num_constraints)
+            .field("num_lookup_constraints", &self.num_lookup_constraints)
+            .field("sink", &self.sink.as_ref().map(|_| "<streaming>"))
+            .finish()
+    }
 }
 
 impl<F: PrimeField> Display for R1CS<F> {
@@ -221,3 +410,109 @@ impl<F: PrimeField> Display for R1CS<F> {
         write!(f, "{output}")
     }
 }
+
+/// The sparse `A`, `B`, `C` matrices and ordered witness vector for an [`R1CS`] instance, in the
+/// form consumed by external SNARK backends.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "F: PrimeField")]
+pub struct R1CSMatrices<F: PrimeField> {
+    a: Vec<Vec<(usize, F)>>,
+    b: Vec<Vec<(usize, F)>>,
+    c: Vec<Vec<(usize, F)>>,
+    witness: Vec<F>,
+    num_instance_variables: usize,
+    num_witness_variables: usize,
+}
+
+impl<F: PrimeField> R1CSMatrices<F> {
+    /// Returns the sparse `A` matrix, one row per constraint.
+    pub fn a(&self) -> &Vec<Vec<(usize, F)>> {
+        &self.a
+    }
+
+    /// Returns the sparse `B` matrix, one row per constraint.
+    pub fn b(&self) -> &Vec<Vec<(usize, F)>> {
+        &self.b
+    }
+
+    /// Returns the sparse `C` matrix, one row per constraint.
+    pub fn c(&self) -> &Vec<Vec<(usize, F)>> {
+        &self.c
+    }
+
+    /// Returns the ordered witness vector, `[one, public_0.., private_0..]`.
+    pub fn witness(&self) -> &Vec<F> {
+        &self.witness
+    }
+
+    /// Returns the number of instance (public) variables, including the leading `one`.
+    pub fn num_instance_variables(&self) -> usize {
+        self.num_instance_variables
+    }
+
+    /// Returns the number of witness (private) variables.
+    pub fn num_witness_variables(&self) -> usize {
+        self.num_witness_variables
+    }
+}
+
+impl<F: PrimeField> ToBytes for R1CSMatrices<F> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        (self.num_instance_variables as u64).write_le(&mut writer)?;
+        (self.num_witness_variables as u64).write_le(&mut writer)?;
+
+        for matrix in [&self.a, &self.b, &self.c] {
+            (matrix.len() as u64).write_le(&mut writer)?;
+            for row in matrix {
+                (row.len() as u64).write_le(&mut writer)?;
+                for (column, coefficient) in row {
+                    (*column as u64).write_le(&mut writer)?;
+                    coefficient.write_le(&mut writer)?;
+                }
+            }
+        }
+
+        (self.witness.len() as u64).write_le(&mut writer)?;
+        for value in &self.witness {
+            value.write_le(&mut writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> FromBytes for R1CSMatrices<F> {
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let num_instance_variables = u64::read_le(&mut reader)? as usize;
+        let num_witness_variables = u64::read_le(&mut reader)? as usize;
+
+        let mut matrices = Vec::with_capacity(3);
+        for _ in 0..3 {
+            let num_rows = u64::read_le(&mut reader)?;
+            let mut matrix = Vec::with_capacity(num_rows as usize);
+            for _ in 0..num_rows {
+                let num_terms = u64::read_le(&mut reader)?;
+                let mut row = Vec::with_capacity(num_terms as usize);
+                for _ in 0..num_terms {
+                    let column = u64::read_le(&mut reader)? as usize;
+                    let coefficient = F::read_le(&mut reader)?;
+                    row.push((column, coefficient));
+                }
+                matrix.push(row);
+            }
+            matrices.push(matrix);
+        }
+        let mut matrices = matrices.into_iter();
+        let a = matrices.next().unwrap();
+        let b = matrices.next().unwrap();
+        let c = matrices.next().unwrap();
+
+        let num_witness = u64::read_le(&mut reader)?;
+        let mut witness = Vec::with_capacity(num_witness as usize);
+        for _ in 0..num_witness {
+            witness.push(F::read_le(&mut reader)?);
+        }
+
+        Ok(Self { a, b, c, witness, num_instance_variables, num_witness_variables })
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{BenchmarkOperations, Operation, SetupOperations, Workload};
+
+use console::{
+    network::Network,
+    program::{Plaintext, PlaintextType, Value, ValueType},
+};
+use snarkvm_synthesizer::Program;
+
+use anyhow::{bail, Result};
+use std::{fs, path::Path, str::FromStr};
+
+/// A workload that benchmarks an arbitrary `.aleo` program, loaded from disk, instead of one
+/// hand-written into the benchmark crate. This lets users benchmark their own real programs
+/// (token mints, lottery draws, tic-tac-toe moves) the way `leo execute <function> <inputs>`
+/// runs a function against both testnet and mainnet programs.
+pub struct ProgramFileWorkload<N: Network> {
+    program: Program<N>,
+    function_name: String,
+    inputs: Vec<Value<N>>,
+    num_executions: usize,
+}
+
+impl<N: Network> ProgramFileWorkload<N> {
+    /// Loads the program at `program_path`, and validates that `function_name` exists in it, that
+    /// `inputs` (each parsed with `Value::from_str`) has as many entries as its declared inputs, and
+    /// that each literal input has its declared literal type. Inputs that are not literals are not
+    /// type-checked, and functions with record, external record or future inputs are rejected.
+    pub fn new(
+        program_path: impl AsRef<Path>,
+        function_name: &str,
+        inputs: &[String],
+        num_executions: usize,
+    ) -> Result<Self> {
+        let program_string = fs::read_to_string(&program_path)?;
+        let program = Program::<N>::from_str(&program_string)?;
+
+        let function_name = function_name.to_string();
+        let function = match program.functions().get(&function_name.parse()?) {
+            Some(function) => function,
+            None => bail!("Function '{function_name}' is not defined in '{}'", program.id()),
+        };
+        let expected_inputs = function.inputs();
+        if expected_inputs.len() != inputs.len() {
+            bail!(
+                "Function '{function_name}' expects {} inputs, found {} inputs",
+                expected_inputs.len(),
+                inputs.len()
+            )
+        }
+
+        let inputs = inputs.iter().map(|input| Value::from_str(input)).collect::<Result<Vec<_>>>()?;
+
+        // Validate each input against the function's declared input type (see `check_input_type`).
+        for (index, (input, expected)) in inputs.iter().zip(expected_inputs).enumerate() {
+            Self::check_input_type(input, expected.value_type()).map_err(|e| {
+                anyhow::anyhow!("Input {index} to '{function_name}' does not match its declared type: {e}")
+            })?;
+        }
+
+        Ok(Self { program, function_name, inputs, num_executions })
+    }
+
+    /// Checks that a literal input has the function's declared literal type. An input that is not
+    /// a literal is accepted without a check; a declared record, external record or future type
+    /// is always an error.
+    fn check_input_type(input: &Value<N>, expected: &ValueType<N>) -> Result<()> {
+        let expected_plaintext_type = match expected {
+            ValueType::Constant(plaintext_type) | ValueType::Public(plaintext_type) | ValueType::Private(plaintext_type) => {
+                plaintext_type
+            }
+            ValueType::Record(..) | ValueType::ExternalRecord(..) | ValueType::Future(..) => {
+                bail!("expected a record, external record, or future, which this workload does not support")
+            }
+        };
+
+        match (input, expected_plaintext_type) {
+            (Value::Plaintext(Plaintext::Literal(literal, ..)), PlaintextType::Literal(expected_literal_type)) => {
+                let found = literal.to_type();
+                if found != *expected_literal_type {
+                    bail!("expected '{expected_literal_type}', found '{found}'")
+                }
+                Ok(())
+            }
+            (Value::Plaintext(Plaintext::Literal(..)), _) => bail!("expected '{expected_plaintext_type}', found a literal"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<N: Network> Workload<N> for ProgramFileWorkload<N> {
+    fn name(&self) -> String {
+        format!("{}/{}/{}_executions", self.program.id(), self.function_name, self.num_executions)
+    }
+
+    fn init(&mut self) -> (SetupOperations<N>, BenchmarkOperations<N>) {
+        let setups = vec![vec![Operation::Deploy(Box::new(self.program.clone()))]];
+
+        // Initialize storage for the benchmark operations.
+        let mut benchmarks = Vec::with_capacity(self.num_executions);
+        // Construct the operations.
+        for _ in 0..self.num_executions {
+            benchmarks.push(Operation::Execute(
+                self.program.id().to_string(),
+                self.function_name.clone(),
+                self.inputs.clone(),
+            ));
+        }
+
+        (setups, benchmarks)
+    }
+}
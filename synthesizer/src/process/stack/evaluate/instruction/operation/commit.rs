@@ -18,6 +18,15 @@ use super::*;
 
 impl<N: Network> Stack<N> {
     /// Evaluates the instruction.
+    ///
+    /// This instruction supports two forms: a 2-operand hiding form, `commit input randomizer`,
+    /// which produces a hiding commitment using the given scalar randomizer (unchanged from
+    /// before); and a 1-operand hash-only form, `commit input`, which produces a binding-only
+    /// commitment by routing to the hash primitive of matching width, with no randomizer. For the
+    /// BHP variants (0-3), both forms return a `Field`. For the Pedersen variants (4-5), the
+    /// hiding form returns a `Group` (`commit_ped*`), but the hash-only form returns a `Field`
+    /// (`hash_ped*` has no group output) — the literal shape is *not* uniform across VARIANT for
+    /// the hash-only form.
     #[inline]
     pub fn evaluate<A: circuit::Aleo<Network = N>>(
         &self,
@@ -25,28 +34,45 @@ impl<N: Network> Stack<N> {
         registers: &mut Registers<N, A>,
     ) -> Result<()> {
         // Ensure the number of operands is correct.
-        if self.operands.len() != 2 {
-            bail!("Instruction '{}' expects 2 operands, found {} operands", Self::opcode(), self.operands.len())
+        let num_operands = self.operands.len();
+        if num_operands != 1 && num_operands != 2 {
+            bail!("Instruction '{}' expects 1 or 2 operands, found {num_operands} operands", Self::opcode())
         }
 
-        // Retrieve the input and randomizer.
+        // Retrieve the input.
         let input = registers.load(stack, &self.operands[0])?;
-        let randomizer = registers.load(stack, &self.operands[1])?;
-        // Retrieve the randomizer.
-        let randomizer = match randomizer {
-            Value::Plaintext(Plaintext::Literal(Literal::Scalar(randomizer), ..)) => randomizer,
-            _ => bail!("Invalid randomizer type for the commit evaluation, expected a scalar"),
-        };
 
-        // Commit the input.
-        let output = match VARIANT {
-            0 => Literal::Field(N::commit_bhp256(&input.to_bits_le(), &randomizer)?),
-            1 => Literal::Field(N::commit_bhp512(&input.to_bits_le(), &randomizer)?),
-            2 => Literal::Field(N::commit_bhp768(&input.to_bits_le(), &randomizer)?),
-            3 => Literal::Field(N::commit_bhp1024(&input.to_bits_le(), &randomizer)?),
-            4 => Literal::Group(N::commit_ped64(&input.to_bits_le(), &randomizer)?),
-            5 => Literal::Group(N::commit_ped128(&input.to_bits_le(), &randomizer)?),
-            _ => bail!("Invalid 'commit' variant: {VARIANT}"),
+        // Commit (or hash) the input, according to the number of operands.
+        let output = match num_operands {
+            // The hash-only form: no randomizer operand is supplied.
+            1 => match VARIANT {
+                0 => Literal::Field(N::hash_bhp256(&input.to_bits_le())?),
+                1 => Literal::Field(N::hash_bhp512(&input.to_bits_le())?),
+                2 => Literal::Field(N::hash_bhp768(&input.to_bits_le())?),
+                3 => Literal::Field(N::hash_bhp1024(&input.to_bits_le())?),
+                4 => Literal::Field(N::hash_ped64(&input.to_bits_le())?),
+                5 => Literal::Field(N::hash_ped128(&input.to_bits_le())?),
+                _ => bail!("Invalid 'commit' variant: {VARIANT}"),
+            },
+            // The hiding form: a scalar randomizer operand is required.
+            2 => {
+                let randomizer = registers.load(stack, &self.operands[1])?;
+                let randomizer = match randomizer {
+                    Value::Plaintext(Plaintext::Literal(Literal::Scalar(randomizer), ..)) => randomizer,
+                    _ => bail!("Invalid randomizer type for the commit evaluation, expected a scalar"),
+                };
+
+                match VARIANT {
+                    0 => Literal::Field(N::commit_bhp256(&input.to_bits_le(), &randomizer)?),
+                    1 => Literal::Field(N::commit_bhp512(&input.to_bits_le(), &randomizer)?),
+                    2 => Literal::Field(N::commit_bhp768(&input.to_bits_le(), &randomizer)?),
+                    3 => Literal::Field(N::commit_bhp1024(&input.to_bits_le(), &randomizer)?),
+                    4 => Literal::Group(N::commit_ped64(&input.to_bits_le(), &randomizer)?),
+                    5 => Literal::Group(N::commit_ped128(&input.to_bits_le(), &randomizer)?),
+                    _ => bail!("Invalid 'commit' variant: {VARIANT}"),
+                }
+            }
+            num_operands => unreachable!("Checked above: found {num_operands} operands"),
         };
         // Store the output.
         registers.store(stack, &self.destination, Value::Plaintext(Plaintext::from(output)))
@@ -0,0 +1,82 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+impl<N: Network> Stack<N> {
+    /// Executes the instruction.
+    ///
+    /// This mirrors `Stack::evaluate` for the `commit` instruction: a 2-operand hiding form,
+    /// `commit input randomizer`, synthesizes a hiding commitment using the given scalar
+    /// randomizer (unchanged from before); a 1-operand hash-only form, `commit input`,
+    /// synthesizes a binding-only commitment by routing to the hash gadget of matching width,
+    /// with no randomizer. For the BHP variants (0-3), both forms return a `Field`. For the
+    /// Pedersen variants (4-5), the hiding form returns a `Group` (`commit_ped*`), but the
+    /// hash-only form returns a `Field` (`hash_ped*` has no group output) — the circuit literal
+    /// shape is *not* uniform across VARIANT for the hash-only form.
+    #[inline]
+    pub fn execute<A: circuit::Aleo<Network = N>>(
+        &self,
+        stack: &Stack<N>,
+        registers: &mut Registers<N, A>,
+    ) -> Result<()> {
+        // Ensure the number of operands is correct.
+        let num_operands = self.operands.len();
+        if num_operands != 1 && num_operands != 2 {
+            bail!("Instruction '{}' expects 1 or 2 operands, found {num_operands} operands", Self::opcode())
+        }
+
+        // Retrieve the input.
+        let input = registers.load_circuit(stack, &self.operands[0])?;
+
+        // Commit (or hash) the input, according to the number of operands.
+        let output = match num_operands {
+            // The hash-only form: no randomizer operand is supplied.
+            1 => match VARIANT {
+                0 => circuit::Literal::Field(A::hash_bhp256(&input.to_bits_le())),
+                1 => circuit::Literal::Field(A::hash_bhp512(&input.to_bits_le())),
+                2 => circuit::Literal::Field(A::hash_bhp768(&input.to_bits_le())),
+                3 => circuit::Literal::Field(A::hash_bhp1024(&input.to_bits_le())),
+                4 => circuit::Literal::Field(A::hash_ped64(&input.to_bits_le())),
+                5 => circuit::Literal::Field(A::hash_ped128(&input.to_bits_le())),
+                _ => bail!("Invalid 'commit' variant: {VARIANT}"),
+            },
+            // The hiding form: a scalar randomizer operand is required.
+            2 => {
+                let randomizer = registers.load_circuit(stack, &self.operands[1])?;
+                let randomizer = match randomizer {
+                    circuit::Value::Plaintext(circuit::Plaintext::Literal(circuit::Literal::Scalar(randomizer), ..)) => {
+                        randomizer
+                    }
+                    _ => bail!("Invalid randomizer type for the commit execution, expected a scalar"),
+                };
+
+                match VARIANT {
+                    0 => circuit::Literal::Field(A::commit_bhp256(&input.to_bits_le(), &randomizer)),
+                    1 => circuit::Literal::Field(A::commit_bhp512(&input.to_bits_le(), &randomizer)),
+                    2 => circuit::Literal::Field(A::commit_bhp768(&input.to_bits_le(), &randomizer)),
+                    3 => circuit::Literal::Field(A::commit_bhp1024(&input.to_bits_le(), &randomizer)),
+                    4 => circuit::Literal::Group(A::commit_ped64(&input.to_bits_le(), &randomizer)),
+                    5 => circuit::Literal::Group(A::commit_ped128(&input.to_bits_le(), &randomizer)),
+                    _ => bail!("Invalid 'commit' variant: {VARIANT}"),
+                }
+            }
+            num_operands => unreachable!("Checked above: found {num_operands} operands"),
+        };
+        // Store the output.
+        registers.store_circuit(stack, &self.destination, circuit::Value::Plaintext(circuit::Plaintext::from(output)))
+    }
+}